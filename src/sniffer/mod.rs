@@ -0,0 +1,56 @@
+// NEEDS DEPENDENCY: this module requires the `pcap` crate (and the system
+// libpcap) to build. Nothing in this tree declares it yet — there is no
+// Cargo.toml here at all — so whoever wires this into the real crate
+// manifest needs to add `pcap` under [dependencies] first.
+mod packet;
+
+pub use packet::NetWolfPacket;
+
+use pcap::{Capture, Device, Error as PcapError};
+
+// Decodes a live interface or a saved .pcap trace into a replayable
+// timeline of NetWolfPacket records.
+pub struct Sniffer {
+    packets: Vec<NetWolfPacket>,
+}
+
+impl Sniffer {
+    pub fn from_file(path: &str) -> Result<Sniffer, PcapError> {
+        let mut capture = Capture::from_file(path)?;
+        Ok(Sniffer {
+            packets: Sniffer::drain(&mut capture),
+        })
+    }
+
+    pub fn from_device(device_name: &str) -> Result<Sniffer, PcapError> {
+        let device = Device::list()?
+            .into_iter()
+            .find(|device| device.name == device_name)
+            .ok_or_else(|| PcapError::PcapError(format!("no such device: {}", device_name)))?;
+        let mut capture = Capture::from_device(device)?.promisc(true).open()?;
+        Ok(Sniffer {
+            packets: Sniffer::drain(&mut capture),
+        })
+    }
+
+    fn drain<T: pcap::Activated>(capture: &mut Capture<T>) -> Vec<NetWolfPacket> {
+        let mut packets = Vec::new();
+        while let Ok(raw) = capture.next_packet() {
+            if let Some(decoded) = NetWolfPacket::decode(raw.data) {
+                packets.push(decoded);
+            }
+        }
+        packets
+    }
+
+    pub fn packets(&self) -> &[NetWolfPacket] {
+        &self.packets
+    }
+
+    // Yields the decoded records in capture order. The raw datagrams are
+    // not kept, so nothing is parsed again, and datagrams that did not
+    // decode when the capture was read are not in the replay.
+    pub fn replay(&self) -> impl Iterator<Item = &NetWolfPacket> {
+        self.packets.iter()
+    }
+}
@@ -0,0 +1,70 @@
+use crate::udp::headers::{PacketHeader, StopAndWaitHeader, TCPHeader};
+use crate::udp::seq_number::SeqNumber;
+
+// A single NetWolf datagram decoded from a capture, live or replayed.
+#[derive(Debug)]
+pub struct NetWolfPacket {
+    pub packet_type: PacketHeader,
+    pub get_port: Option<u16>,
+    pub rdt_port: Option<u16>,
+    pub file_name: Option<String>,
+    pub seq_num: Option<SeqNumber>,
+}
+
+impl NetWolfPacket {
+    fn bare(packet_type: PacketHeader) -> NetWolfPacket {
+        NetWolfPacket {
+            packet_type,
+            get_port: None,
+            rdt_port: None,
+            file_name: None,
+            seq_num: None,
+        }
+    }
+
+    // None if payload doesn't match any known NetWolf header.
+    pub fn decode(payload: &[u8]) -> Option<NetWolfPacket> {
+        let leading = String::from_utf8_lossy(payload);
+        let packet_type = PacketHeader::packet_type(&leading);
+
+        match packet_type {
+            PacketHeader::Unrecognized => None,
+            PacketHeader::TCPReceiverExistence => {
+                let header = TCPHeader::try_from_bytes(payload).ok()?;
+                Some(NetWolfPacket {
+                    packet_type,
+                    get_port: Some(header.udp_get_port),
+                    rdt_port: None,
+                    file_name: Some(header.file_name),
+                    seq_num: None,
+                })
+            }
+            PacketHeader::StopWaitData
+            | PacketHeader::GoBackN
+            | PacketHeader::SRepeat
+            | PacketHeader::StopWaitACK
+            | PacketHeader::StopWaitNAK => {
+                let tag_len = match packet_type {
+                    PacketHeader::StopWaitData => PacketHeader::stop_and_wait_data().len(),
+                    PacketHeader::GoBackN => PacketHeader::go_back_n().len(),
+                    PacketHeader::SRepeat => PacketHeader::selective_repeat().len(),
+                    PacketHeader::StopWaitACK => PacketHeader::stop_and_wait_ack().len(),
+                    PacketHeader::StopWaitNAK => PacketHeader::stop_and_wait_nak().len(),
+                    _ => unreachable!(),
+                };
+                let header = StopAndWaitHeader::try_from_bytes(&payload[tag_len..]).ok()?;
+                Some(NetWolfPacket {
+                    packet_type,
+                    get_port: Some(header.get_port()),
+                    rdt_port: Some(header.rdt_port()),
+                    file_name: Some(header.file_name().to_string()),
+                    seq_num: Some(header.seq_num),
+                })
+            }
+            PacketHeader::Disc
+            | PacketHeader::GET
+            | PacketHeader::GETACK
+            | PacketHeader::UDPReceiverExistence => Some(NetWolfPacket::bare(packet_type)),
+        }
+    }
+}
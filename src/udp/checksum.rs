@@ -0,0 +1,33 @@
+// RFC 1071 Internet checksum: sum big-endian 16-bit words into a u32,
+// zero-pad a trailing odd byte, fold the carries back in, then complement.
+pub fn compute_checksum(data: &[u8]) -> u16 {
+    let mut sum: u32 = 0;
+    let mut chunks = data.chunks_exact(2);
+    for word in &mut chunks {
+        sum += u16::from_be_bytes([word[0], word[1]]) as u32;
+    }
+    if let [last] = *chunks.remainder() {
+        sum += u16::from_be_bytes([last, 0]) as u32;
+    }
+    while (sum >> 16) != 0 {
+        sum = (sum >> 16) + (sum & 0xffff);
+    }
+    !(sum as u16)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_answer_vector() {
+        // RFC 1071 section 3 worked example.
+        let data = [0x00, 0x01, 0xf2, 0x03, 0xf4, 0xf5, 0xf6, 0xf7];
+        assert_eq!(compute_checksum(&data), 0x220d);
+    }
+
+    #[test]
+    fn odd_length_input_is_zero_padded() {
+        assert_eq!(compute_checksum(&[0xff]), compute_checksum(&[0xff, 0x00]));
+    }
+}
@@ -1,6 +1,10 @@
 use std::fmt;
 use std::mem::size_of;
 
+use crate::udp::checksum::compute_checksum;
+use crate::udp::parse_error::ParseError;
+use crate::udp::seq_number::SeqNumber;
+
 #[derive(PartialEq, Eq, Debug)]
 pub enum PacketHeader {
     Disc,
@@ -130,20 +134,34 @@ impl TCPHeader {
         }
     }
 
-    pub fn from_string(packet: String) -> TCPHeader {
-        let mut packet_lines = packet.lines();
-        let packet_type = packet_lines.next().unwrap();
-        let conn_type = PacketHeader::packet_type(&packet_type);
-        let udp_get_port = packet_lines.next().unwrap().parse::<u16>().unwrap_or(0);
-        let file_name = packet_lines.next().unwrap_or("").to_string();
-        TCPHeader::new(conn_type, udp_get_port, file_name)
+    // conn_type tag line (text, newline-terminated), then udp_get_port as
+    // two raw big-endian bytes, then file_name bytes — not decimal text.
+    pub fn try_from_bytes(buf: &[u8]) -> Result<TCPHeader, ParseError> {
+        let newline = buf.iter().position(|&b| b == b'\n').ok_or(ParseError::TooShort)?;
+        let tag = std::str::from_utf8(&buf[..=newline]).map_err(|_| ParseError::BadUtf8)?;
+        let conn_type = PacketHeader::packet_type(tag);
+        if conn_type == PacketHeader::Unrecognized {
+            return Err(ParseError::UnrecognizedType);
+        }
+        let port_start = newline + 1;
+        let port_size = size_of::<u16>();
+        if buf.len() < port_start + port_size {
+            return Err(ParseError::TooShort);
+        }
+        let udp_get_port = u16::from_be_bytes([buf[port_start], buf[port_start + 1]]);
+        let file_name = std::str::from_utf8(&buf[port_start + port_size..])
+            .map_err(|_| ParseError::BadUtf8)?
+            .to_string();
+        Ok(TCPHeader::new(conn_type, udp_get_port, file_name))
     }
 
-    pub fn to_string(&self) -> String {
-        format!(
-            "{}\n{}\n{}",
-            self.conn_type, self.udp_get_port, self.file_name
-        )
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let tag = self.conn_type.to_string();
+        let mut bytes = tag.trim_end_matches('\n').as_bytes().to_vec();
+        bytes.push(b'\n');
+        bytes.extend_from_slice(&self.udp_get_port.to_be_bytes());
+        bytes.extend_from_slice(self.file_name.as_bytes());
+        bytes
     }
 }
 
@@ -165,6 +183,8 @@ pub struct StopAndWaitHeader {
     header_size: u16,
     get_port: u16,
     rdt_port: u16,
+    pub seq_num: SeqNumber,
+    checksum: u16,
     file_name: String,
 }
 
@@ -173,34 +193,154 @@ impl StopAndWaitHeader {
         header_size: u16,
         get_port: u16,
         rdt_port: u16,
+        seq_num: SeqNumber,
+        checksum: u16,
         file_name: String,
     ) -> StopAndWaitHeader {
         StopAndWaitHeader {
             header_size,
             get_port,
             rdt_port,
+            seq_num,
+            checksum,
             file_name,
         }
     }
 
-    fn u16_from_bytes(buf: &[u8]) -> u16 {
-        let byte_str = std::str::from_utf8(buf).unwrap();
-        byte_str.parse::<u16>().unwrap()
+    pub fn get_port(&self) -> u16 {
+        self.get_port
     }
 
-    pub fn from_string(buf: &[u8]) -> StopAndWaitHeader {
-        let size = size_of::<u16>();
-        let header_size = StopAndWaitHeader::u16_from_bytes(&buf[..size]);
-        let header_usize: usize = header_size.into();
-        let get_port = StopAndWaitHeader::u16_from_bytes(&buf[size..size * 2]);
-        let rdt_port = StopAndWaitHeader::u16_from_bytes(&buf[size * 2..size * 3]);
-        let file_name = std::str::from_utf8(&buf[size * 3..header_usize])
-            .unwrap()
+    pub fn rdt_port(&self) -> u16 {
+        self.rdt_port
+    }
+
+    pub fn file_name(&self) -> &str {
+        &self.file_name
+    }
+
+    // Fixed-size prefix: header_size, get_port, rdt_port (u16 each),
+    // seq_num (u32), checksum (u16), in that order, all big-endian.
+    const PREFIX_LEN: usize = size_of::<u16>() * 4 + size_of::<u32>();
+
+    // Bounds-checked against buf.len() (and the declared header_size)
+    // before indexing, so a bad datagram is an Err, not a panic.
+    pub fn try_from_bytes(buf: &[u8]) -> Result<StopAndWaitHeader, ParseError> {
+        if buf.len() < StopAndWaitHeader::PREFIX_LEN {
+            return Err(ParseError::TooShort);
+        }
+        let header_size = u16::from_be_bytes([buf[0], buf[1]]);
+        let get_port = u16::from_be_bytes([buf[2], buf[3]]);
+        let rdt_port = u16::from_be_bytes([buf[4], buf[5]]);
+        let seq_num = SeqNumber::new(u32::from_be_bytes([buf[6], buf[7], buf[8], buf[9]]));
+        let checksum = u16::from_be_bytes([buf[10], buf[11]]);
+
+        let header_usize = header_size as usize;
+        if header_usize < StopAndWaitHeader::PREFIX_LEN || header_usize > buf.len() {
+            return Err(ParseError::BadHeaderSize);
+        }
+        let file_name = std::str::from_utf8(&buf[StopAndWaitHeader::PREFIX_LEN..header_usize])
+            .map_err(|_| ParseError::BadUtf8)?
             .to_string();
-        StopAndWaitHeader::new(header_size, get_port, rdt_port, file_name)
+        Ok(StopAndWaitHeader::new(
+            header_size,
+            get_port,
+            rdt_port,
+            seq_num,
+            checksum,
+            file_name,
+        ))
     }
 
     pub fn packet_size(file_name: String) -> usize {
-        size_of::<u16>() * 3 + file_name.as_bytes().len()
+        StopAndWaitHeader::PREFIX_LEN + file_name.as_bytes().len()
+    }
+
+    // Recomputes the checksum over buf with the checksum field itself
+    // excluded and compares it against self.checksum (ACK vs NAK).
+    pub fn verify(&self, buf: &[u8]) -> bool {
+        let size = size_of::<u16>();
+        let checksum_offset = StopAndWaitHeader::PREFIX_LEN - size;
+        if buf.len() < StopAndWaitHeader::PREFIX_LEN {
+            return false;
+        }
+        let mut unchecksummed = Vec::with_capacity(buf.len() - size);
+        unchecksummed.extend_from_slice(&buf[..checksum_offset]);
+        unchecksummed.extend_from_slice(&buf[checksum_offset + size..]);
+        compute_checksum(&unchecksummed) == self.checksum
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_rejects_a_too_short_buffer_instead_of_panicking() {
+        let header = StopAndWaitHeader::new(0, 0, 0, SeqNumber::default(), 0, String::new());
+        assert!(!header.verify(&[0u8; 1]));
+    }
+
+    fn assert_err<T>(result: Result<T, ParseError>, expected: ParseError) {
+        match result {
+            Err(err) => assert_eq!(err, expected),
+            Ok(_) => panic!("expected Err({:?}), got Ok", expected),
+        }
+    }
+
+    #[test]
+    fn stop_and_wait_try_from_bytes_too_short() {
+        assert_err(StopAndWaitHeader::try_from_bytes(&[0u8; 4]), ParseError::TooShort);
+    }
+
+    #[test]
+    fn stop_and_wait_try_from_bytes_bad_header_size() {
+        let mut buf = vec![0u8; StopAndWaitHeader::PREFIX_LEN];
+        // header_size (first u16) declares more bytes than the buffer has.
+        buf[0..2].copy_from_slice(&u16::MAX.to_be_bytes());
+        assert_err(StopAndWaitHeader::try_from_bytes(&buf), ParseError::BadHeaderSize);
+    }
+
+    #[test]
+    fn stop_and_wait_try_from_bytes_bad_utf8() {
+        let mut buf = vec![0u8; StopAndWaitHeader::PREFIX_LEN + 1];
+        let header_size = buf.len() as u16;
+        buf[0..2].copy_from_slice(&header_size.to_be_bytes());
+        buf[StopAndWaitHeader::PREFIX_LEN] = 0xff; // invalid UTF-8 byte
+        assert_err(StopAndWaitHeader::try_from_bytes(&buf), ParseError::BadUtf8);
+    }
+
+    #[test]
+    fn tcp_header_try_from_bytes_too_short() {
+        assert_err(
+            TCPHeader::try_from_bytes(PacketHeader::get().as_bytes()),
+            ParseError::TooShort,
+        );
+    }
+
+    #[test]
+    fn tcp_header_try_from_bytes_unrecognized_type() {
+        assert_err(
+            TCPHeader::try_from_bytes(b"BOGUS\n1234\nfile.txt"),
+            ParseError::UnrecognizedType,
+        );
+    }
+
+    #[test]
+    fn tcp_header_try_from_bytes_bad_utf8() {
+        // Valid tag + port, but an invalid UTF-8 byte in the file name.
+        let mut buf = b"GET\n".to_vec();
+        buf.extend_from_slice(&0u16.to_be_bytes());
+        buf.push(0xff);
+        assert_err(TCPHeader::try_from_bytes(&buf), ParseError::BadUtf8);
+    }
+
+    #[test]
+    fn tcp_header_round_trips_through_to_bytes() {
+        let header = TCPHeader::new(PacketHeader::TCPReceiverExistence, 4242, "a.txt".to_string());
+        let bytes = header.to_bytes();
+        let parsed = TCPHeader::try_from_bytes(&bytes).unwrap();
+        assert_eq!(parsed.udp_get_port, 4242);
+        assert_eq!(parsed.file_name, "a.txt");
     }
 }
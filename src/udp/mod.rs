@@ -0,0 +1,6 @@
+pub mod checksum;
+pub mod connection;
+pub mod headers;
+pub mod parse_error;
+pub mod selective_repeat;
+pub mod seq_number;
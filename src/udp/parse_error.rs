@@ -0,0 +1,28 @@
+use std::fmt;
+
+// Why a malformed datagram fails to decode, instead of just panicking.
+#[derive(PartialEq, Eq, Debug)]
+pub enum ParseError {
+    // Fewer bytes than the fixed part of the header needs.
+    TooShort,
+    // A text field (packet tag or file name) is not valid UTF-8.
+    BadUtf8,
+    // Declared header size doesn't fit inside the received buffer.
+    BadHeaderSize,
+    // Leading packet tag didn't match any known PacketHeader variant.
+    UnrecognizedType,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            ParseError::TooShort => "buffer is shorter than the fixed header",
+            ParseError::BadUtf8 => "field is not valid UTF-8",
+            ParseError::BadHeaderSize => "declared header size does not fit the buffer",
+            ParseError::UnrecognizedType => "packet tag did not match a known header type",
+        };
+        write!(f, "{}", message)
+    }
+}
+
+impl std::error::Error for ParseError {}
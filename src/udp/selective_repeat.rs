@@ -0,0 +1,262 @@
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use crate::udp::headers::StopAndWaitHeader;
+use crate::udp::seq_number::SeqNumber;
+
+// An unacknowledged packet with its own retransmission timer: unlike
+// Go-Back-N, Selective Repeat times out and resends one packet at a time.
+struct PendingPacket {
+    bytes: Vec<u8>,
+    deadline: Instant,
+}
+
+// Sender half of Selective Repeat: a window of in-flight packets, each
+// with its own timer, acknowledged individually rather than cumulatively.
+pub struct SelectiveRepeatSender {
+    base: SeqNumber,
+    next_seq: SeqNumber,
+    window_size: usize,
+    timeout: Duration,
+    pending: HashMap<SeqNumber, PendingPacket>,
+    acked: HashSet<SeqNumber>,
+}
+
+impl SelectiveRepeatSender {
+    pub fn new(window_size: usize, timeout: Duration) -> SelectiveRepeatSender {
+        SelectiveRepeatSender {
+            base: SeqNumber::default(),
+            next_seq: SeqNumber::default(),
+            window_size,
+            timeout,
+            pending: HashMap::new(),
+            acked: HashSet::new(),
+        }
+    }
+
+    pub fn can_send(&self) -> bool {
+        self.next_seq - self.base < self.window_size
+    }
+
+    // Places bytes at the next sequence number and starts its timer.
+    // Returns None if the window is already full.
+    pub fn send(&mut self, bytes: Vec<u8>, now: Instant) -> Option<SeqNumber> {
+        if !self.can_send() {
+            return None;
+        }
+        let seq = self.next_seq;
+        self.pending.insert(
+            seq,
+            PendingPacket {
+                bytes,
+                deadline: now + self.timeout,
+            },
+        );
+        self.next_seq = self.next_seq + 1usize;
+        Some(seq)
+    }
+
+    // Marks seq delivered and slides base past every contiguously-acked
+    // entry that follows it.
+    pub fn on_ack(&mut self, seq: SeqNumber) {
+        if self.pending.remove(&seq).is_none() {
+            return;
+        }
+        self.acked.insert(seq);
+        while self.acked.remove(&self.base) {
+            self.base = self.base + 1usize;
+        }
+    }
+
+    // Every packet whose individual timer expired by now, with its timer
+    // reset. Only the packets that actually timed out are retransmitted,
+    // never the whole window.
+    pub fn expired(&mut self, now: Instant) -> Vec<(SeqNumber, Vec<u8>)> {
+        let mut timed_out = Vec::new();
+        for (&seq, packet) in self.pending.iter_mut() {
+            if packet.deadline <= now {
+                timed_out.push((seq, packet.bytes.clone()));
+                packet.deadline = now + self.timeout;
+            }
+        }
+        timed_out
+    }
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub enum ReceiveOutcome {
+    // Checksum passed and seq falls inside the receive window: ACK it.
+    Buffered,
+    // Checksum failed: NAK it instead.
+    Corrupt,
+    // Already delivered in a previous window slide: the original ACK must
+    // have been lost, since the sender retransmitted it. Re-ACK without
+    // re-buffering, or the sender's base can never advance past it.
+    DuplicateBelowWindow,
+    // Too far ahead of the window to buffer; silently dropped, no ACK.
+    TooFarAhead,
+}
+
+// Receiver half of Selective Repeat: buffers out-of-order packets within
+// the receive window and delivers them in order once the low end fills.
+pub struct SelectiveRepeatReceiver {
+    base: SeqNumber,
+    window_size: usize,
+    buffer: HashMap<SeqNumber, Vec<u8>>,
+}
+
+impl SelectiveRepeatReceiver {
+    pub fn new(window_size: usize) -> SelectiveRepeatReceiver {
+        SelectiveRepeatReceiver {
+            base: SeqNumber::default(),
+            window_size,
+            buffer: HashMap::new(),
+        }
+    }
+
+    // A correctly-received packet is ACKed even out of order; a corrupt
+    // one should be NAK'd instead of buffered.
+    pub fn accept(
+        &mut self,
+        header: &StopAndWaitHeader,
+        raw: &[u8],
+        payload: Vec<u8>,
+    ) -> ReceiveOutcome {
+        if !header.verify(raw) {
+            return ReceiveOutcome::Corrupt;
+        }
+        if header.seq_num < self.base {
+            return ReceiveOutcome::DuplicateBelowWindow;
+        }
+        if header.seq_num - self.base >= self.window_size {
+            return ReceiveOutcome::TooFarAhead;
+        }
+        self.buffer.insert(header.seq_num, payload);
+        ReceiveOutcome::Buffered
+    }
+
+    // Drains every contiguous packet starting at the window base,
+    // advancing the window past what was just delivered.
+    pub fn deliver_ready(&mut self) -> Vec<Vec<u8>> {
+        let mut delivered = Vec::new();
+        while let Some(bytes) = self.buffer.remove(&self.base) {
+            delivered.push(bytes);
+            self.base = self.base + 1usize;
+        }
+        delivered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::udp::checksum::compute_checksum;
+
+    // Builds a (header, raw bytes) pair that StopAndWaitHeader::verify
+    // accepts, matching the on-wire layout from headers.rs.
+    fn build_packet(seq: u32, file_name: &str) -> (StopAndWaitHeader, Vec<u8>) {
+        let header_size = StopAndWaitHeader::packet_size(file_name.to_string()) as u16;
+        let mut unchecksummed = Vec::new();
+        unchecksummed.extend_from_slice(&header_size.to_be_bytes());
+        unchecksummed.extend_from_slice(&1u16.to_be_bytes());
+        unchecksummed.extend_from_slice(&2u16.to_be_bytes());
+        unchecksummed.extend_from_slice(&seq.to_be_bytes());
+        unchecksummed.extend_from_slice(file_name.as_bytes());
+        let checksum = compute_checksum(&unchecksummed);
+
+        let mut raw = Vec::new();
+        raw.extend_from_slice(&unchecksummed[..10]);
+        raw.extend_from_slice(&checksum.to_be_bytes());
+        raw.extend_from_slice(file_name.as_bytes());
+
+        let header = StopAndWaitHeader::new(
+            header_size,
+            1,
+            2,
+            SeqNumber::new(seq),
+            checksum,
+            file_name.to_string(),
+        );
+        (header, raw)
+    }
+
+    #[test]
+    fn sender_retransmits_only_the_packet_that_timed_out() {
+        let mut sender = SelectiveRepeatSender::new(4, Duration::from_secs(1));
+        let t0 = Instant::now();
+        sender.send(vec![1], t0).unwrap();
+        sender.send(vec![2], t0 + Duration::from_millis(500)).unwrap();
+
+        let expired = sender.expired(t0 + Duration::from_millis(1100));
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].1, vec![1]);
+    }
+
+    #[test]
+    fn sender_slides_base_only_past_contiguous_acks() {
+        let mut sender = SelectiveRepeatSender::new(4, Duration::from_secs(1));
+        let now = Instant::now();
+        let seq0 = sender.send(vec![0], now).unwrap();
+        let seq1 = sender.send(vec![1], now).unwrap();
+        sender.send(vec![2], now).unwrap();
+
+        sender.on_ack(seq1);
+        assert_eq!(sender.base, seq0);
+
+        sender.on_ack(seq0);
+        assert_eq!(sender.base, seq0 + 2usize);
+    }
+
+    #[test]
+    fn receiver_buffers_out_of_order_and_delivers_once_contiguous() {
+        let mut receiver = SelectiveRepeatReceiver::new(4);
+        let (header1, raw1) = build_packet(1, "f");
+        let (header0, raw0) = build_packet(0, "f");
+
+        assert_eq!(
+            receiver.accept(&header1, &raw1, vec![1]),
+            ReceiveOutcome::Buffered
+        );
+        assert!(receiver.deliver_ready().is_empty());
+
+        assert_eq!(
+            receiver.accept(&header0, &raw0, vec![0]),
+            ReceiveOutcome::Buffered
+        );
+        assert_eq!(receiver.deliver_ready(), vec![vec![0], vec![1]]);
+    }
+
+    #[test]
+    fn receiver_reacks_a_duplicate_retransmitted_after_its_ack_was_lost() {
+        let mut receiver = SelectiveRepeatReceiver::new(4);
+        for seq in 0..4 {
+            let (header, raw) = build_packet(seq, "f");
+            assert_eq!(receiver.accept(&header, &raw, vec![seq as u8]), ReceiveOutcome::Buffered);
+        }
+        assert_eq!(receiver.deliver_ready().len(), 4);
+
+        // The ACK for seq 2 was lost, so the sender retransmits it after
+        // base has already slid past it.
+        let (header2, raw2) = build_packet(2, "f");
+        assert_eq!(
+            receiver.accept(&header2, &raw2, vec![2]),
+            ReceiveOutcome::DuplicateBelowWindow
+        );
+        // Not re-buffered: there's nothing new to deliver.
+        assert!(receiver.deliver_ready().is_empty());
+    }
+
+    #[test]
+    fn receiver_rejects_a_corrupted_packet() {
+        let (header, mut raw) = build_packet(0, "f");
+        *raw.last_mut().unwrap() ^= 0xff;
+        assert_eq!(
+            receiver_accept_once(header, raw),
+            ReceiveOutcome::Corrupt
+        );
+    }
+
+    fn receiver_accept_once(header: StopAndWaitHeader, raw: Vec<u8>) -> ReceiveOutcome {
+        SelectiveRepeatReceiver::new(4).accept(&header, &raw, vec![0])
+    }
+}
@@ -0,0 +1,77 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Sub};
+
+// Go-Back-N / Selective Repeat sequence number. Stored as i32, serialized
+// as u32; Add/Sub wrap at the u32 boundary and ordering uses wrapping
+// subtraction so "is A before B" stays correct across the wrap.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct SeqNumber(i32);
+
+impl SeqNumber {
+    pub fn new(value: u32) -> SeqNumber {
+        SeqNumber(value as i32)
+    }
+
+    pub fn as_u32(&self) -> u32 {
+        self.0 as u32
+    }
+}
+
+impl Add<usize> for SeqNumber {
+    type Output = SeqNumber;
+
+    fn add(self, rhs: usize) -> SeqNumber {
+        SeqNumber(self.0.wrapping_add(rhs as i32))
+    }
+}
+
+impl Sub<usize> for SeqNumber {
+    type Output = SeqNumber;
+
+    fn sub(self, rhs: usize) -> SeqNumber {
+        SeqNumber(self.0.wrapping_sub(rhs as i32))
+    }
+}
+
+impl Sub<SeqNumber> for SeqNumber {
+    type Output = usize;
+
+    fn sub(self, rhs: SeqNumber) -> usize {
+        (self.0.wrapping_sub(rhs.0)) as usize
+    }
+}
+
+impl PartialOrd for SeqNumber {
+    fn partial_cmp(&self, other: &SeqNumber) -> Option<Ordering> {
+        self.0.wrapping_sub(other.0).partial_cmp(&0)
+    }
+}
+
+impl fmt::Display for SeqNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0 as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orders_correctly_across_the_wrap() {
+        assert!(SeqNumber::new(u32::MAX) < SeqNumber::new(0));
+        assert!(SeqNumber::new(0) > SeqNumber::new(u32::MAX));
+    }
+
+    #[test]
+    fn add_and_sub_wrap_at_u32_bounds() {
+        assert_eq!(SeqNumber::new(u32::MAX) + 1usize, SeqNumber::new(0));
+        assert_eq!(SeqNumber::new(0) - 1usize, SeqNumber::new(u32::MAX));
+    }
+
+    #[test]
+    fn distance_wraps_too() {
+        assert_eq!(SeqNumber::new(0) - SeqNumber::new(u32::MAX), 1);
+    }
+}